@@ -0,0 +1,85 @@
+//! A small, dependency-free CRC-32 (the IEEE/gzip variant) so footer validation doesn't need to
+//! pull in a whole CRC crate just for this one polynomial.
+
+const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+fn build_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+        for _ in 0..8 {
+            c = if c & 1 != 0 { POLYNOMIAL ^ (c >> 1) } else { c >> 1 };
+        }
+        *entry = c;
+    }
+    table
+}
+
+lazy_static! {
+    static ref TABLE: [u32; 256] = build_table();
+}
+
+/// Computes the CRC-32 of `data` in one shot, matching the value stored in a gzip footer.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.finalize()
+}
+
+/// An incremental CRC-32 accumulator, for callers (like the streaming decoder) that want to feed
+/// bytes through as they arrive instead of buffering the whole thing first.
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+
+    pub fn new() -> Self {
+        Crc32 { crc: 0xFFFF_FFFF }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.crc = TABLE[((self.crc ^ byte as u32) & 0xFF) as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    pub fn finalize(self) -> u32 {
+        self.crc ^ 0xFFFF_FFFF
+    }
+
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Crc32::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_crc32_empty() {
+        assert_eq!(crc32(&[]), 0x0000_0000);
+    }
+
+    #[test]
+    fn test_crc32_known_value() {
+        // Matches `cksum`'s CRC-32 variant against the canonical "123456789" check string.
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_crc32_incremental_matches_one_shot() {
+        let data = b"the quick brown fox jumps over the lazy dog";
+        let mut incremental = Crc32::new();
+        for chunk in data.chunks(7) {
+            incremental.update(chunk);
+        }
+        assert_eq!(incremental.finalize(), crc32(data));
+    }
+
+}
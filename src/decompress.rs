@@ -0,0 +1,78 @@
+//! DEFLATE decompression for a parsed [`GzipFile`](../types/struct.GzipFile.html), with the
+//! footer's CRC-32 and input size used to verify the result rather than just taking it on faith.
+
+use types::GzipFile;
+use error::DecompressError;
+use crc32::crc32;
+
+impl<'a> GzipFile<'a> {
+
+    /// Inflates `compressed_blocks` as a raw DEFLATE stream (gzip wraps a bare deflate stream,
+    /// with no zlib header) and validates the result against this file's footer: the CRC-32 of
+    /// the decompressed bytes must match `footer.crc`, and its length (mod 2^32) must match
+    /// `footer.input_size`.
+    pub fn decompress(&self) -> Result<Vec<u8>, DecompressError> {
+        let decompressed = ::miniz_oxide::inflate::decompress_to_vec(&self.compressed_blocks)
+            .map_err(|e| DecompressError::Inflate(format!("{:?}", e)))?;
+
+        let actual_crc = crc32(&decompressed);
+        if actual_crc != self.footer.crc {
+            return Err(DecompressError::CrcMismatch { expected: self.footer.crc, actual: actual_crc });
+        }
+
+        let actual_size = decompressed.len() as u32;
+        if actual_size != self.footer.input_size {
+            return Err(DecompressError::SizeMismatch { expected: self.footer.input_size, actual: actual_size });
+        }
+
+        Ok(decompressed)
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use types::{GzipFile, GzipFooter, GzipHeader};
+    use crc32::crc32;
+    use error::DecompressError;
+
+    fn gzip_file_for(plain: &[u8]) -> GzipFile<'static> {
+        let compressed_blocks = ::miniz_oxide::deflate::compress_to_vec(plain, 6);
+        GzipFile {
+            header: GzipHeader::new(),
+            footer: GzipFooter { crc: crc32(plain), input_size: plain.len() as u32 },
+            compressed_blocks,
+        }
+    }
+
+    #[test]
+    fn decompress_round_trips_valid_data() {
+        let plain = b"The quick brown fox jumps over the lazy dog.".repeat(100);
+        let gzip_file = gzip_file_for(&plain);
+        assert_eq!(gzip_file.decompress().unwrap(), plain);
+    }
+
+    #[test]
+    fn decompress_detects_crc_mismatch() {
+        let plain = b"hello, world";
+        let mut gzip_file = gzip_file_for(plain);
+        gzip_file.footer.crc ^= 0xffff_ffff;
+        match gzip_file.decompress() {
+            Err(DecompressError::CrcMismatch { .. }) => {}
+            other => assert!(false, "expected a CrcMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decompress_detects_size_mismatch() {
+        let plain = b"hello, world";
+        let mut gzip_file = gzip_file_for(plain);
+        gzip_file.footer.input_size += 1;
+        match gzip_file.decompress() {
+            Err(DecompressError::SizeMismatch { .. }) => {}
+            other => assert!(false, "expected a SizeMismatch, got {:?}", other),
+        }
+    }
+
+}
@@ -0,0 +1,199 @@
+//! Serializing a [`GzipHeader`](../types/struct.GzipHeader.html)/[`GzipFooter`]
+//! (../types/struct.GzipFooter.html) back into bytes, and a `Write`-based encoder built on top
+//! of that which compresses what's written to it and appends a correct footer on `finish()`.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use crc32::crc32;
+use types::{ExtraField, Flags, GzipFooter, GzipHeader, OperatingSystem};
+
+impl<'a> GzipHeader<'a> {
+
+    /// A header with sane defaults for a freshly-created archive: DEFLATE compression, no
+    /// optional fields set, and `modified_time_as_secs_since_epoch` left at the epoch (callers
+    /// that care should set it explicitly).
+    pub fn new() -> GzipHeader<'static> {
+        GzipHeader {
+            compression_method: ::types::CompressionMethod::Deflate,
+            flags: Flags { ftext: false, fhcrc: false, fextra: false, fname: false, fcomment: false },
+            modified_time_as_secs_since_epoch: Duration::from_secs(0),
+            extra_flags: ::types::ExtraFlags::Unknown,
+            operating_system: OperatingSystem::Unix,
+            extra_field: None,
+            original_filename: None,
+            file_comment: None,
+            header_crc: None,
+        }
+    }
+
+    /// Serializes this header in the exact byte layout `gzip_header` parses: the fixed 10-byte
+    /// block, followed by whichever of FEXTRA/FNAME/FCOMMENT/FHCRC the flags call for.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        out.write_all(&[0x1f, 0x8b])?;
+        out.write_all(&[self.compression_method.into()])?;
+        out.write_all(&[self.flags.into()])?;
+        out.write_u32::<LittleEndian>(self.modified_time_as_secs_since_epoch.as_secs() as u32)?;
+        out.write_all(&[self.extra_flags.into()])?;
+        out.write_all(&[self.operating_system.into()])?;
+
+        if let Some(ref extra_field) = self.extra_field {
+            write_extra_field(extra_field, out)?;
+        }
+        if let Some(ref filename) = self.original_filename {
+            out.write_all(filename.as_bytes())?;
+            out.write_all(&[0x00])?;
+        }
+        if let Some(ref comment) = self.file_comment {
+            out.write_all(comment.as_bytes())?;
+            out.write_all(&[0x00])?;
+        }
+        if let Some(crc) = self.header_crc {
+            out.write_u16::<LittleEndian>(crc)?;
+        }
+
+        Ok(())
+    }
+
+}
+
+fn write_extra_field<'a, W: Write>(extra_field: &ExtraField<'a>, out: &mut W) -> io::Result<()> {
+    let mut buf = Vec::new();
+    for sub_field in &extra_field.sub_fields {
+        buf.push(sub_field.id1);
+        buf.push(sub_field.id2);
+        buf.write_u16::<LittleEndian>(sub_field.data.len() as u16)?;
+        buf.extend_from_slice(sub_field.data);
+    }
+    out.write_u16::<LittleEndian>(buf.len() as u16)?;
+    out.write_all(&buf)
+}
+
+impl GzipFooter {
+
+    /// Serializes this footer as the trailing 8 bytes `gzip_footer` parses: little-endian CRC-32
+    /// followed by little-endian input size.
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        out.write_u32::<LittleEndian>(self.crc)?;
+        out.write_u32::<LittleEndian>(self.input_size)
+    }
+
+}
+
+/// A GZIP encoder analogous to libflate's `gzip::Encoder`: bytes written to it are buffered,
+/// DEFLATE-compressed, and wrapped in a header/footer when `finish()` is called.
+pub struct GzipEncoder<W: Write> {
+    inner: W,
+    header: GzipHeader<'static>,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> GzipEncoder<W> {
+
+    /// Creates an encoder that will write a header with `GzipHeader::new()` defaults.
+    pub fn new(inner: W) -> Self {
+        GzipEncoder::with_header(inner, GzipHeader::new())
+    }
+
+    /// Creates an encoder that will write the given header ahead of the compressed data.
+    pub fn with_header(inner: W, header: GzipHeader<'static>) -> Self {
+        GzipEncoder { inner, header, buffer: Vec::new() }
+    }
+
+    /// Compresses everything written so far, and writes the header, compressed DEFLATE stream,
+    /// and footer (with the running CRC-32 and input size) to the underlying writer, which is
+    /// then handed back to the caller.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.header.write(&mut self.inner)?;
+
+        let compressed = ::miniz_oxide::deflate::compress_to_vec(&self.buffer, 6);
+        self.inner.write_all(&compressed)?;
+
+        let footer = GzipFooter { crc: crc32(&self.buffer), input_size: self.buffer.len() as u32 };
+        footer.write(&mut self.inner)?;
+
+        Ok(self.inner)
+    }
+
+}
+
+impl<W: Write> Write for GzipEncoder<W> {
+
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use nom::IResult::Done;
+    use types::{CompressionMethod, ExtraFlags};
+    use {gzip_file, gzip_footer, gzip_header};
+
+    #[test]
+    fn header_write_round_trips_through_gzip_header() {
+        let mut header = GzipHeader::new();
+        header.original_filename = Some(String::from("sample.txt"));
+        header.flags.fname = true;
+        header.extra_flags = ExtraFlags::MaximumCompression;
+
+        let mut bytes = Vec::new();
+        header.write(&mut bytes).unwrap();
+
+        match gzip_header(&bytes) {
+            Done(remaining, parsed) => {
+                assert_eq!(remaining.len(), 0);
+                assert_eq!(parsed.compression_method, CompressionMethod::Deflate);
+                assert_eq!(parsed.extra_flags, ExtraFlags::MaximumCompression);
+                assert_eq!(parsed.original_filename, Some(String::from("sample.txt")));
+                assert_eq!(parsed.header_crc, None);
+            },
+            unexpected => assert!(false, "Expected a GZIP header, got this instead: {:?}", unexpected),
+        }
+    }
+
+    #[test]
+    fn footer_write_round_trips_through_gzip_footer() {
+        let footer = GzipFooter { crc: 0xdead_beef, input_size: 12_345 };
+
+        let mut bytes = Vec::new();
+        footer.write(&mut bytes).unwrap();
+
+        match gzip_footer(&bytes) {
+            Done(remaining, parsed) => {
+                assert_eq!(remaining.len(), 0);
+                assert_eq!(parsed.crc, footer.crc);
+                assert_eq!(parsed.input_size, footer.input_size);
+            },
+            unexpected => assert!(false, "Expected a GZIP footer, got this instead: {:?}", unexpected),
+        }
+    }
+
+    #[test]
+    fn encoder_round_trips_through_gzip_file_and_decompress() {
+        let plain = b"The quick brown fox jumps over the lazy dog. ".repeat(200);
+
+        let mut encoder = GzipEncoder::new(Vec::new());
+        encoder.write_all(&plain).unwrap();
+        let encoded = encoder.finish().unwrap();
+
+        match gzip_file(&encoded) {
+            Done(remaining, gzip_file) => {
+                assert_eq!(remaining.len(), 0);
+                assert_eq!(gzip_file.decompress().unwrap(), plain);
+            },
+            unexpected => assert!(false, "Expected a GZIP file, got this instead: {:?}", unexpected),
+        }
+    }
+
+}
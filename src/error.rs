@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// Errors that can occur when turning a [`GzipFile`](../types/struct.GzipFile.html)'s
+/// `compressed_blocks` back into the original bytes.
+#[derive(Debug)]
+pub enum DecompressError {
+    /// The DEFLATE stream in `compressed_blocks` could not be inflated.
+    Inflate(String),
+    /// The CRC-32 of the decompressed bytes didn't match `GzipFooter::crc`.
+    CrcMismatch { expected: u32, actual: u32 },
+    /// The decompressed length didn't match `GzipFooter::input_size` (mod 2^32).
+    SizeMismatch { expected: u32, actual: u32 },
+}
+
+impl fmt::Display for DecompressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecompressError::Inflate(ref msg) =>
+                write!(f, "DEFLATE decompression failed: {}", msg),
+            DecompressError::CrcMismatch { expected, actual } =>
+                write!(f, "CRC-32 mismatch: footer says {:#010x}, decompressed data has {:#010x}", expected, actual),
+            DecompressError::SizeMismatch { expected, actual } =>
+                write!(f, "input size mismatch: footer says {} bytes, decompressed data has {} bytes", expected, actual),
+        }
+    }
+}
+
+impl ::std::error::Error for DecompressError {
+    fn description(&self) -> &str {
+        match *self {
+            DecompressError::Inflate(_) => "DEFLATE decompression failed",
+            DecompressError::CrcMismatch { .. } => "CRC-32 mismatch",
+            DecompressError::SizeMismatch { .. } => "input size mismatch",
+        }
+    }
+}
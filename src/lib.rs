@@ -3,27 +3,26 @@
 //!
 //! # Notes on this parser
 //!
-//! ## TL;DR
-//!
-//! This parser assumes the GZIP data contains only a single compressed file that goes until EOF.
-//!
-//! ## Details
-//!
-//! While in theory multiple files can be in a single GZIP stream by simply concatenating multiple
-//! GZIP files together (see [section 2.2](https://tools.ietf.org/html/rfc1952#page-5]) of the RFC),
-//! in practice it appears that at least the GZIP and 7z utilities (in Linux) do not correctly
-//! support this. For two files cat'd together they both report the header of the first file with
-//! the footer (uncompressed size of the file) from the second. Decompression of such a file
-//! with the gzip utility results in the uncompressed contents of both files concatenated together
-//! in a single file instead of two files with separated content. IMHO if this feature of the GZIP
-//! format can't be used in any practical sense there is no point in spending time writing a
-//! theoretically correct but far more involved (and slower!) parser here.
+//! `gzip_file` parses a single GZIP member, the common case. GZIP streams can also be a
+//! concatenation of multiple members (see [section 2.2](https://tools.ietf.org/html/rfc1952#page-5)
+//! of the RFC) -- `bgzip` and `cat a.gz b.gz` both produce these -- so `gzip_members` is also
+//! provided to parse (and `decompress_all` to decompress) every member in a stream in order.
 
 pub mod types;
+pub mod error;
+pub mod crc32;
+mod decompress;
+pub mod members;
+pub mod stream;
+pub mod encode;
 use types::*;
 
 #[macro_use]
 extern crate nom;
+#[macro_use]
+extern crate lazy_static;
+extern crate miniz_oxide;
+extern crate byteorder;
 
 use nom::{le_u16, le_u32};
 use nom::Endianness::Little;
@@ -85,15 +84,22 @@ named!(pub gzip_header<GzipHeader>, do_parse!(
     })
 ));
 
-named!(pub gzip_footer<GzipFooter>, do_parse!(
+named!(footer_fields<GzipFooter>, do_parse!(
        crc: footer_crc32
     >> input_size: input_size
-    >> eof!()
     >>
 
     (GzipFooter { crc, input_size })
 ));
 
+named!(pub gzip_footer<GzipFooter>, do_parse!(
+       footer: footer_fields
+    >> eof!()
+    >>
+
+    (footer)
+));
+
 /// This will probably be pretty slow; you'll likely want to use `gzip_header` and then make use of
 /// the GZIP stream directly from there, passing in the last 8 bytes to `gzip_footer` if necessary.
 named!(pub gzip_file<GzipFile>, do_parse! (
@@ -106,6 +112,39 @@ named!(pub gzip_file<GzipFile>, do_parse! (
     (gzip_file)
 ));
 
+/// Parses one member out of a possibly-concatenated GZIP stream: a header, followed by exactly
+/// as much of the DEFLATE stream as the inflater says belongs to this member, followed by its
+/// footer. Unlike `gzip_file`, this does not require the footer to be at EOF, so `many1!` can
+/// keep calling it to walk every member in a multi-member stream.
+fn gzip_member(input: &[u8]) -> ::nom::IResult<&[u8], GzipFile> {
+    use nom::IResult;
+
+    let (after_header, header) = match gzip_header(input) {
+        IResult::Done(rest, header) => (rest, header),
+        IResult::Error(e) => return IResult::Error(e),
+        IResult::Incomplete(n) => return IResult::Incomplete(n),
+    };
+
+    let (compressed_blocks, consumed) = match members::deflate_member_length(after_header) {
+        Ok(result) => result,
+        Err(_) => return IResult::Error(::nom::ErrorKind::Custom(1)),
+    };
+
+    if after_header.len() < consumed + 8 {
+        return IResult::Incomplete(::nom::Needed::Size(consumed + 8 - after_header.len()));
+    }
+
+    let footer = match footer_fields(&after_header[consumed..consumed + 8]) {
+        IResult::Done(_, footer) => footer,
+        IResult::Error(e) => return IResult::Error(e),
+        IResult::Incomplete(n) => return IResult::Incomplete(n),
+    };
+
+    IResult::Done(&after_header[consumed + 8..], GzipFile { header, footer, compressed_blocks })
+}
+
+named!(pub gzip_members<Vec<GzipFile> >, many1!(complete!(gzip_member)));
+
 #[cfg(test)]
 mod tests {
 
@@ -0,0 +1,92 @@
+//! Support for GZIP streams made up of multiple concatenated members (RFC 1952 section 2.2).
+//!
+//! The trick that makes `gzip_members` implementable is that we don't have to guess where one
+//! member's DEFLATE stream ends and the next member's header begins -- the inflater itself
+//! reports how many input bytes it consumed to produce a complete decompressed stream, so we
+//! can hand it the rest of the buffer and trust that number.
+
+use miniz_oxide::inflate::core::{decompress, inflate_flags, DecompressorOxide};
+use miniz_oxide::inflate::TINFLStatus;
+
+use error::DecompressError;
+use types::GzipFile;
+
+/// Runs the raw DEFLATE decompressor just far enough to find where the current member's
+/// compressed stream ends within `data`, returning the compressed bytes belonging to that
+/// member and how many bytes of `data` they occupied (so the caller can pick up parsing the
+/// footer, and then the next member, right after).
+pub fn deflate_member_length(data: &[u8]) -> Result<(Vec<u8>, usize), DecompressError> {
+    let mut inflater = DecompressorOxide::new();
+    let mut out = vec![0u8; 32 * 1024];
+    let mut out_pos = 0;
+    let mut in_pos = 0;
+
+    loop {
+        let (status, bytes_in, bytes_out) = decompress(
+            &mut inflater,
+            &data[in_pos..],
+            &mut out,
+            out_pos,
+            inflate_flags::TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF,
+        );
+        in_pos += bytes_in;
+        out_pos += bytes_out;
+
+        match status {
+            TINFLStatus::Done => return Ok((data[..in_pos].to_vec(), in_pos)),
+            TINFLStatus::HasMoreOutput => out.resize(out.len() * 2, 0),
+            TINFLStatus::NeedsMoreInput =>
+                return Err(DecompressError::Inflate("unexpected end of DEFLATE stream".into())),
+            other => return Err(DecompressError::Inflate(format!("{:?}", other))),
+        }
+    }
+}
+
+/// Decompresses every member of a multi-member GZIP stream (as parsed by `gzip_members`),
+/// concatenating their decompressed contents in order, exactly as the `gzip` CLI does for
+/// `cat a.gz b.gz`.
+pub fn decompress_all(members: &[GzipFile]) -> Result<Vec<u8>, DecompressError> {
+    let mut decompressed = Vec::new();
+    for member in members {
+        decompressed.extend(member.decompress()?);
+    }
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use types::{GzipFooter, GzipHeader};
+    use crc32::crc32;
+
+    #[test]
+    fn deflate_member_length_stops_at_the_member_boundary() {
+        let first = b"hello, ".to_vec();
+        let second = b"world!".to_vec();
+        let first_compressed = ::miniz_oxide::deflate::compress_to_vec(&first, 6);
+        let second_compressed = ::miniz_oxide::deflate::compress_to_vec(&second, 6);
+
+        let mut both = first_compressed.clone();
+        both.extend_from_slice(&second_compressed);
+
+        let (member, consumed) = deflate_member_length(&both).unwrap();
+        assert_eq!(consumed, first_compressed.len());
+        assert_eq!(::miniz_oxide::inflate::decompress_to_vec(&member).unwrap(), first);
+    }
+
+    #[test]
+    fn decompress_all_concatenates_members_in_order() {
+        let parts: [&[u8]; 2] = [b"hello, ", b"world!"];
+        let members: Vec<GzipFile> = parts.iter().map(|part| {
+            GzipFile {
+                header: GzipHeader::new(),
+                footer: GzipFooter { crc: crc32(part), input_size: part.len() as u32 },
+                compressed_blocks: ::miniz_oxide::deflate::compress_to_vec(part, 6),
+            }
+        }).collect();
+
+        assert_eq!(decompress_all(&members).unwrap(), b"hello, world!".to_vec());
+    }
+
+}
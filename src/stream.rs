@@ -0,0 +1,197 @@
+//! An incremental GZIP decoder for callers that receive data in pieces (a socket, a `Read`)
+//! rather than having the whole file in memory up front.
+//!
+//! `gzip_header`/`gzip_file` already report `nom::IResult::Incomplete` when fed a truncated
+//! buffer, but there's no way to hand them more bytes and pick up where they left off --
+//! `GzipStreamDecoder` is that missing piece of state. It keeps only a fixed 32KB DEFLATE
+//! dictionary window in memory (not the whole decompressed output), handing each newly-produced
+//! chunk back to the caller as soon as it's available.
+
+use miniz_oxide::inflate::core::{decompress, inflate_flags, DecompressorOxide};
+use miniz_oxide::inflate::TINFLStatus;
+use nom::IResult;
+
+use crc32::Crc32;
+use error::DecompressError;
+use footer_fields;
+use gzip_header;
+
+/// DEFLATE back-references never reach further back than this, so it's all the dictionary
+/// `decompress` needs to keep around (matches miniz_oxide's own `TINFL_LZ_DICT_SIZE`).
+const WINDOW_SIZE: usize = 32 * 1024;
+
+enum State {
+    Header,
+    Body(Box<DecompressorOxide>),
+    Footer,
+    Done,
+}
+
+/// Decodes a single GZIP member off a growing buffer, one chunk at a time.
+pub struct GzipStreamDecoder {
+    buffer: Vec<u8>,
+    state: State,
+    crc: Crc32,
+    decompressed_len: u64,
+    // Fixed-size circular DEFLATE dictionary window; `out_pos` is always `< WINDOW_SIZE` and
+    // wraps back to 0 once the inflater fills it, rather than growing without bound.
+    out: Vec<u8>,
+    out_pos: usize,
+}
+
+impl GzipStreamDecoder {
+
+    pub fn new() -> Self {
+        GzipStreamDecoder {
+            buffer: Vec::new(),
+            state: State::Header,
+            crc: Crc32::new(),
+            decompressed_len: 0,
+            out: Vec::new(),
+            out_pos: 0,
+        }
+    }
+
+    /// Feeds another chunk of input into the decoder, returning whatever decompressed bytes
+    /// could be produced from it. An empty `Ok(vec![])` means the decoder made progress (or
+    /// needs more input) but has nothing new to hand back yet; it is not itself an error.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<u8>, DecompressError> {
+        self.buffer.extend_from_slice(chunk);
+        let mut produced = Vec::new();
+
+        loop {
+            match self.state {
+                State::Header => {
+                    match gzip_header(&self.buffer) {
+                        IResult::Done(rest, _header) => {
+                            let consumed = self.buffer.len() - rest.len();
+                            self.buffer.drain(..consumed);
+                            self.out = vec![0u8; WINDOW_SIZE];
+                            self.out_pos = 0;
+                            self.state = State::Body(Box::new(DecompressorOxide::new()));
+                        }
+                        IResult::Incomplete(_) => return Ok(produced),
+                        IResult::Error(e) => return Err(DecompressError::Inflate(format!("{:?}", e))),
+                    }
+                }
+                State::Body(ref mut inflater) => {
+                    if self.buffer.is_empty() {
+                        return Ok(produced);
+                    }
+
+                    let start_pos = self.out_pos;
+                    let (status, bytes_in, bytes_out) = decompress(
+                        inflater,
+                        &self.buffer,
+                        &mut self.out,
+                        start_pos,
+                        inflate_flags::TINFL_FLAG_HAS_MORE_INPUT,
+                    );
+                    self.buffer.drain(..bytes_in);
+
+                    // The dictionary window wraps at WINDOW_SIZE, so a single call's output can
+                    // straddle the end of `self.out` and continue from its start.
+                    let mut new_bytes = Vec::with_capacity(bytes_out);
+                    let mut pos = start_pos;
+                    let mut left = bytes_out;
+                    while left > 0 {
+                        let take = left.min(WINDOW_SIZE - pos);
+                        new_bytes.extend_from_slice(&self.out[pos..pos + take]);
+                        left -= take;
+                        pos = (pos + take) % WINDOW_SIZE;
+                    }
+                    self.out_pos = pos;
+
+                    self.crc.update(&new_bytes);
+                    self.decompressed_len += new_bytes.len() as u64;
+                    produced.extend_from_slice(&new_bytes);
+
+                    match status {
+                        TINFLStatus::Done => {
+                            self.state = State::Footer;
+                            continue;
+                        }
+                        TINFLStatus::HasMoreOutput => continue,
+                        TINFLStatus::NeedsMoreInput => return Ok(produced),
+                        other => return Err(DecompressError::Inflate(format!("{:?}", other))),
+                    }
+                }
+                State::Footer => {
+                    if self.buffer.len() < 8 {
+                        return Ok(produced);
+                    }
+
+                    let footer = match footer_fields(&self.buffer[..8]) {
+                        IResult::Done(_, footer) => footer,
+                        IResult::Error(e) => return Err(DecompressError::Inflate(format!("{:?}", e))),
+                        IResult::Incomplete(_) => return Ok(produced),
+                    };
+                    self.buffer.drain(..8);
+
+                    let crc = ::std::mem::take(&mut self.crc).finalize();
+                    if crc != footer.crc {
+                        return Err(DecompressError::CrcMismatch { expected: footer.crc, actual: crc });
+                    }
+                    let len = self.decompressed_len as u32;
+                    if len != footer.input_size {
+                        return Err(DecompressError::SizeMismatch { expected: footer.input_size, actual: len });
+                    }
+
+                    self.state = State::Done;
+                    return Ok(produced);
+                }
+                State::Done => return Ok(produced),
+            }
+        }
+    }
+
+}
+
+impl Default for GzipStreamDecoder {
+    fn default() -> Self {
+        GzipStreamDecoder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use types::{GzipFooter, GzipHeader};
+    use crc32::crc32;
+
+    fn build_gzip_bytes(plain: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        GzipHeader::new().write(&mut bytes).unwrap();
+        bytes.extend(::miniz_oxide::deflate::compress_to_vec(plain, 6));
+        GzipFooter { crc: crc32(plain), input_size: plain.len() as u32 }.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn push_decodes_a_whole_stream_in_one_call() {
+        let plain = b"The quick brown fox jumps over the lazy dog. ".repeat(200);
+        let bytes = build_gzip_bytes(&plain);
+
+        let mut decoder = GzipStreamDecoder::new();
+        let produced = decoder.push(&bytes).unwrap();
+        assert_eq!(produced, plain);
+    }
+
+    #[test]
+    fn push_decodes_repetitive_data_fed_in_small_chunks() {
+        // Regression test: repetitive text compresses into DEFLATE back-references, which rely
+        // on the dictionary window surviving across `push()` calls -- feeding it 16 bytes at a
+        // time used to panic/corrupt output before the window was kept intact between calls.
+        let plain = b"The quick brown fox jumps over the lazy dog. ".repeat(200);
+        let bytes = build_gzip_bytes(&plain);
+
+        let mut decoder = GzipStreamDecoder::new();
+        let mut produced = Vec::new();
+        for chunk in bytes.chunks(16) {
+            produced.extend(decoder.push(chunk).unwrap());
+        }
+        assert_eq!(produced, plain);
+    }
+
+}
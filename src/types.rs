@@ -1,4 +1,4 @@
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum CompressionMethod {
     Reserved0,
     Reserved1,
@@ -32,7 +32,27 @@ impl From<u8> for CompressionMethod {
 
 }
 
-#[derive(Debug, PartialEq)]
+impl From<CompressionMethod> for u8 {
+
+    fn from(method: CompressionMethod) -> Self {
+        use CompressionMethod::*;
+        match method {
+            Reserved0 => 0,
+            Reserved1 => 1,
+            Reserved2 => 2,
+            Reserved3 => 3,
+            Reserved4 => 4,
+            Reserved5 => 5,
+            Reserved6 => 6,
+            Reserved7 => 7,
+            Deflate => 8,
+            Unknown => 0xff,
+        }
+    }
+
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Flags {
     pub ftext:    bool,
     pub fhcrc:    bool,
@@ -55,7 +75,22 @@ impl From<u8> for Flags {
 
 }
 
-#[derive(Debug, PartialEq)]
+impl From<Flags> for u8 {
+
+    /// The inverse of `From<u8> for Flags`: packs the five flag bits back into a single byte.
+    fn from(flags: Flags) -> Self {
+        let mut byte = 0u8;
+        if flags.ftext    { byte |= 0b0000_0001; }
+        if flags.fhcrc    { byte |= 0b0000_0010; }
+        if flags.fextra   { byte |= 0b0000_0100; }
+        if flags.fname    { byte |= 0b0000_1000; }
+        if flags.fcomment { byte |= 0b0001_0000; }
+        byte
+    }
+
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ExtraFlags {
     MaximumCompression,
     FastestAlgorithm,
@@ -75,7 +110,20 @@ impl From<u8> for ExtraFlags {
 
 }
 
-#[derive(Debug, PartialEq)]
+impl From<ExtraFlags> for u8 {
+
+    fn from(extra_flags: ExtraFlags) -> Self {
+        use ExtraFlags::*;
+        match extra_flags {
+            MaximumCompression => 2,
+            FastestAlgorithm => 4,
+            Unknown => 0,
+        }
+    }
+
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum OperatingSystem {
     Fat,
     Amiga,
@@ -119,6 +167,31 @@ impl From<u8> for OperatingSystem {
 
 }
 
+impl From<OperatingSystem> for u8 {
+
+    fn from(os: OperatingSystem) -> Self {
+        use OperatingSystem::*;
+        match os {
+            Fat => 0,
+            Amiga => 1,
+            Vms => 2,
+            Unix => 3,
+            VmCms => 4,
+            AtariTos => 5,
+            Hpfs => 6,
+            Macintosh => 7,
+            Zsystem => 8,
+            Cpm => 9,
+            Tops20 => 10,
+            Ntfs => 11,
+            Qdos => 12,
+            AcornRiscos => 13,
+            Unknown => 0xff,
+        }
+    }
+
+}
+
 #[derive(Debug, PartialEq)]
 pub struct SubField<'a> {
     pub id1: u8,
@@ -1,6 +1,8 @@
 extern crate nom;
 extern crate nom_gzip;
 
+use std::io::Write;
+
 use nom::IResult::Done;
 
 use nom_gzip::*;
@@ -10,6 +12,10 @@ const SAMPLE_GZIP_FILE: &'static [u8] = include_bytes!("sample.txt.gz");
 const HEADER_SIZE: usize = 10 + (10 + 1); // 10 bytes fixed + (original filename + null terminator)
 const FOOTER_SIZE: usize = 8;
 
+// Two independently-gzipped members ("hello, " and "world!\n") simply concatenated together,
+// the way `cat a.gz b.gz` or `bgzip` would produce.
+const MULTI_MEMBER_GZIP_FILE: &'static [u8] = include_bytes!("multi_member.txt.gz");
+
 fn validate_header(header: &GzipHeader) {
     assert_eq!(header.compression_method, CompressionMethod::Deflate);
     assert!(! header.flags.ftext);
@@ -17,7 +23,7 @@ fn validate_header(header: &GzipHeader) {
     assert!(! header.flags.fextra);
     assert!(header.flags.fname);
     assert!(! header.flags.fcomment);
-    assert_eq!(header.modified_time_as_secs_since_epoch.as_secs(), 0x599e86e7);
+    assert_eq!(header.modified_time_as_secs_since_epoch.as_secs(), 0);
     assert_eq!(header.extra_flags, ExtraFlags::MaximumCompression);
     assert_eq!(header.operating_system, OperatingSystem::Unix);
     assert_eq!(header.extra_field, None);
@@ -27,8 +33,8 @@ fn validate_header(header: &GzipHeader) {
 }
 
 fn validate_footer(footer: &GzipFooter) {
-    assert_eq!(footer.crc, 0xbd47c3dc);
-    assert_eq!(footer.input_size, 0x0000738f);
+    assert_eq!(footer.crc, 0xa936_99c2);
+    assert_eq!(footer.input_size, 90_000);
 }
 
 #[test]
@@ -64,3 +70,61 @@ fn it_whole_file() {
         unexpected => assert!(false, "Expected a GZIP file, got this instead: {:?}", unexpected),
     }
 }
+
+#[test]
+fn it_decompresses_and_verifies_the_footer() {
+    match gzip_file(SAMPLE_GZIP_FILE) {
+        Done(_, gz_file) => {
+            let decompressed = gz_file.decompress().expect("sample.txt.gz should decompress cleanly");
+            assert_eq!(decompressed.len(), 90_000);
+            assert!(decompressed.starts_with(b"The quick brown fox jumps over the lazy dog."));
+        },
+        unexpected => assert!(false, "Expected a GZIP file, got this instead: {:?}", unexpected),
+    }
+}
+
+#[test]
+fn it_round_trips_sample_through_decode_then_encode_then_decode() {
+    let original = match gzip_file(SAMPLE_GZIP_FILE) {
+        Done(_, gz_file) => gz_file.decompress().expect("sample.txt.gz should decompress cleanly"),
+        unexpected => {
+            assert!(false, "Expected a GZIP file, got this instead: {:?}", unexpected);
+            return;
+        }
+    };
+
+    let mut header = GzipHeader::new();
+    header.flags.fname = true;
+    header.extra_flags = ExtraFlags::MaximumCompression;
+    header.original_filename = Some(String::from("sample.txt"));
+
+    let mut encoder = nom_gzip::encode::GzipEncoder::with_header(Vec::new(), header);
+    encoder.write_all(&original).unwrap();
+    let encoded = encoder.finish().unwrap();
+
+    // The re-encoded bytes won't necessarily match SAMPLE_GZIP_FILE byte-for-byte (our encoder
+    // and the `gzip` binary that produced the fixture needn't pick identical DEFLATE encodings
+    // for the same input), but decoding what we just encoded must reproduce the same content.
+    match gzip_file(&encoded) {
+        Done(remaining, gz_file) => {
+            assert_eq!(remaining.len(), 0);
+            validate_header(&gz_file.header);
+            assert_eq!(gz_file.decompress().unwrap(), original);
+        },
+        unexpected => assert!(false, "Expected a GZIP file, got this instead: {:?}", unexpected),
+    }
+}
+
+#[test]
+fn it_parses_and_decompresses_a_multi_member_stream() {
+    match gzip_members(MULTI_MEMBER_GZIP_FILE) {
+        Done(remaining, members) => {
+            assert_eq!(remaining.len(), 0);
+            assert_eq!(members.len(), 2);
+            let decompressed = nom_gzip::members::decompress_all(&members)
+                .expect("both members should decompress cleanly");
+            assert_eq!(decompressed, b"hello, world!\n".to_vec());
+        },
+        unexpected => assert!(false, "Expected two GZIP members, got this instead: {:?}", unexpected),
+    }
+}